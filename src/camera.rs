@@ -0,0 +1,99 @@
+use wgpu::util::DeviceExt;
+
+// wgpu's NDC z-range is 0..1, unlike OpenGL's -1..1, so the projection matrix
+// needs this correction baked in before it reaches the shader.
+#[rustfmt::skip]
+const OPENGL_TO_WGPU_MATRIX: glam::Mat4 = glam::Mat4::from_cols_array(&[
+    1.0, 0.0, 0.0, 0.0,
+    0.0, 1.0, 0.0, 0.0,
+    0.0, 0.0, 0.5, 0.0,
+    0.0, 0.0, 0.5, 1.0,
+]);
+
+/// A perspective view/projection pair driving the vertex shader.
+#[derive(Debug, Clone, Copy)]
+pub struct Camera {
+    pub eye: glam::Vec3,
+    pub target: glam::Vec3,
+    pub up: glam::Vec3,
+    pub aspect: f32,
+    pub fovy: f32,
+    pub znear: f32,
+    pub zfar: f32,
+}
+
+impl Camera {
+    pub fn build_view_projection_matrix(&self) -> glam::Mat4 {
+        let view = glam::Mat4::look_at_rh(self.eye, self.target, self.up);
+        let proj = glam::Mat4::perspective_rh_gl(self.fovy, self.aspect, self.znear, self.zfar);
+        OPENGL_TO_WGPU_MATRIX * proj * view
+    }
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+struct CameraUniform {
+    view_proj: [[f32; 4]; 4],
+}
+
+impl CameraUniform {
+    fn new(camera: &Camera) -> Self {
+        CameraUniform {
+            view_proj: camera.build_view_projection_matrix().to_cols_array_2d(),
+        }
+    }
+}
+
+/// GPU-side resources feeding the camera's view/projection matrix to the vertex stage.
+#[derive(Debug)]
+pub struct GpuCamera {
+    pub bind_group_layout: wgpu::BindGroupLayout,
+    pub bind_group: wgpu::BindGroup,
+    buffer: wgpu::Buffer,
+}
+
+impl GpuCamera {
+    pub fn new(device: &wgpu::Device, camera: &Camera) -> Self {
+        let uniform = CameraUniform::new(camera);
+
+        let buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("camera buffer"),
+            contents: bytemuck::cast_slice(&[uniform]),
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+        });
+
+        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("camera bind group layout"),
+            entries: &[wgpu::BindGroupLayoutEntry {
+                binding: 0,
+                visibility: wgpu::ShaderStages::VERTEX,
+                ty: wgpu::BindingType::Buffer {
+                    ty: wgpu::BufferBindingType::Uniform,
+                    has_dynamic_offset: false,
+                    min_binding_size: None,
+                },
+                count: None,
+            }],
+        });
+
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("camera bind group"),
+            layout: &bind_group_layout,
+            entries: &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: buffer.as_entire_binding(),
+            }],
+        });
+
+        GpuCamera {
+            bind_group_layout,
+            bind_group,
+            buffer,
+        }
+    }
+
+    pub fn update(&self, queue: &wgpu::Queue, camera: &Camera) {
+        let uniform = CameraUniform::new(camera);
+        queue.write_buffer(&self.buffer, 0, bytemuck::cast_slice(&[uniform]));
+    }
+}
@@ -0,0 +1,92 @@
+/// Debug overlay rendered on top of the scene with `egui`.
+pub struct Gui {
+    pub context: egui::Context,
+    state: egui_winit::State,
+    renderer: egui_wgpu::Renderer,
+}
+
+impl std::fmt::Debug for Gui {
+    // `egui::Context`/`egui_winit::State`/`egui_wgpu::Renderer` don't implement
+    // `Debug`, so there is nothing more meaningful to print here.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Gui").finish_non_exhaustive()
+    }
+}
+
+impl Gui {
+    pub fn new(device: &wgpu::Device, window: &winit::window::Window, color_format: wgpu::TextureFormat) -> Self {
+        let context = egui::Context::default();
+        let viewport_id = context.viewport_id();
+        let state = egui_winit::State::new(context.clone(), viewport_id, window, None, None, None);
+        // The overlay pass below has no depth attachment (it draws on top of the
+        // already depth-tested scene), so the renderer's own pipeline must not be
+        // built against a depth format either, or wgpu's pass/pipeline validation
+        // rejects it the first time a frame is drawn.
+        let renderer = egui_wgpu::Renderer::new(device, color_format, None, 1, false);
+
+        Gui {
+            context,
+            state,
+            renderer,
+        }
+    }
+
+    /// Feeds a window event into egui's input handling. Returns whether egui consumed it.
+    pub fn on_window_event(
+        &mut self,
+        window: &winit::window::Window,
+        event: &winit::event::WindowEvent,
+    ) -> bool {
+        self.state.on_window_event(window, event).consumed
+    }
+
+    /// Runs the egui frame, tessellates it, and draws it into a second render pass on `view`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn render(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        encoder: &mut wgpu::CommandEncoder,
+        window: &winit::window::Window,
+        view: &wgpu::TextureView,
+        screen_descriptor: egui_wgpu::ScreenDescriptor,
+        run_ui: impl FnOnce(&egui::Context),
+    ) {
+        let raw_input = self.state.take_egui_input(window);
+        let full_output = self.context.run(raw_input, run_ui);
+        self.state
+            .handle_platform_output(window, full_output.platform_output);
+
+        let primitives = self
+            .context
+            .tessellate(full_output.shapes, full_output.pixels_per_point);
+
+        for (id, delta) in &full_output.textures_delta.set {
+            self.renderer.update_texture(device, queue, *id, delta);
+        }
+        self.renderer
+            .update_buffers(device, queue, encoder, &primitives, &screen_descriptor);
+
+        {
+            let mut rpass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("egui render pass"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Load,
+                        store: wgpu::StoreOp::Store,
+                    },
+                })],
+                depth_stencil_attachment: None,
+                timestamp_writes: None,
+                occlusion_query_set: None,
+            });
+            self.renderer.render(&mut rpass, &primitives, &screen_descriptor);
+        }
+
+        for id in &full_output.textures_delta.free {
+            self.renderer.free_texture(id);
+        }
+    }
+}
@@ -0,0 +1,36 @@
+use std::collections::HashSet;
+
+use winit::keyboard::KeyCode;
+
+/// Tracks which keys are currently held and where the cursor last was.
+#[derive(Debug, Default)]
+pub struct InputState {
+    pressed: HashSet<KeyCode>,
+    cursor_position: Option<(f64, f64)>,
+}
+
+impl InputState {
+    pub fn set_key(&mut self, key: KeyCode, pressed: bool) {
+        if pressed {
+            self.pressed.insert(key);
+        } else {
+            self.pressed.remove(&key);
+        }
+    }
+
+    pub fn is_pressed(&self, key: KeyCode) -> bool {
+        self.pressed.contains(&key)
+    }
+
+    pub fn set_cursor_position(&mut self, position: (f64, f64)) {
+        self.cursor_position = Some(position);
+    }
+
+    pub fn clear_cursor_position(&mut self) {
+        self.cursor_position = None;
+    }
+
+    pub fn cursor_position(&self) -> Option<(f64, f64)> {
+        self.cursor_position
+    }
+}
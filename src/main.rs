@@ -4,7 +4,46 @@ use winit::dpi::PhysicalSize;
 use winit::event_loop::{ControlFlow, EventLoop};
 use winit::{application::ApplicationHandler, window::Window};
 
+mod camera;
+mod gui;
+mod input;
+mod mesh;
+mod time;
+
 static APP_NAME: &str = "Meticulous";
+const DEPTH_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Depth32Float;
+
+// Default geometry shown until a model is loaded via `load_obj`.
+const TRIANGLE_VERTICES: [mesh::Vertex; 3] = [
+    mesh::Vertex { position: [0.0, 0.5, 0.0], color: [1.0, 0.0, 0.0] },
+    mesh::Vertex { position: [-0.5, -0.5, 0.0], color: [0.0, 1.0, 0.0] },
+    mesh::Vertex { position: [0.5, -0.5, 0.0], color: [0.0, 0.0, 1.0] },
+];
+const TRIANGLE_INDICES: [u32; 3] = [0, 1, 2];
+
+// Cycled through with the `C` key.
+const CLEAR_COLORS: [wgpu::Color; 3] = [wgpu::Color::GREEN, wgpu::Color::BLUE, wgpu::Color::BLACK];
+
+const CAMERA_MOVE_SPEED: f32 = 2.0; // units per second
+const CAMERA_ORBIT_SPEED: f32 = 0.5; // radians per second, while continuous mode is on
+
+fn create_depth_view(device: &wgpu::Device, config: &wgpu::SurfaceConfiguration) -> wgpu::TextureView {
+    let texture = device.create_texture(&wgpu::TextureDescriptor {
+        label: Some("depth texture"),
+        size: wgpu::Extent3d {
+            width: config.width.max(1),
+            height: config.height.max(1),
+            depth_or_array_layers: 1,
+        },
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format: DEPTH_FORMAT,
+        usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+        view_formats: &[],
+    });
+    texture.create_view(&wgpu::TextureViewDescriptor::default())
+}
 
 #[derive(Debug)]
 struct Application {
@@ -12,6 +51,15 @@ struct Application {
     surface: Option<wgpu::Surface<'static>>,
     surface_config: Option<wgpu::SurfaceConfiguration>,
     render_pipeline: Option<wgpu::RenderPipeline>,
+    depth_view: Option<wgpu::TextureView>,
+    mesh: Option<mesh::Mesh>,
+    camera: Option<camera::Camera>,
+    gpu_camera: Option<camera::GpuCamera>,
+    input: input::InputState,
+    clear_color_index: usize,
+    frame_clock: Option<time::FrameClock>,
+    continuous: bool,
+    gui: Option<gui::Gui>,
     gpu_instance: wgpu::Instance,
     gpu_adapter: wgpu::Adapter,
     gpu_device: Arc<wgpu::Device>,
@@ -35,7 +83,9 @@ impl Application {
                 &wgpu::DeviceDescriptor {
                     label: None,
                     required_features: wgpu::Features::empty(),
-                    required_limits: wgpu::Limits::default().using_resolution(gpu_adapter.limits()),
+                    // `downlevel_defaults` keeps us within what mobile GLES backends
+                    // (Android) support; `default()` assumes desktop-class limits.
+                    required_limits: wgpu::Limits::downlevel_defaults().using_resolution(gpu_adapter.limits()),
                 },
                 None,
             )
@@ -47,27 +97,107 @@ impl Application {
             surface: None,
             surface_config: None,
             render_pipeline: None,
+            depth_view: None,
+            mesh: None,
+            camera: None,
+            gpu_camera: None,
+            input: input::InputState::default(),
+            clear_color_index: 0,
+            frame_clock: None,
+            continuous: false,
+            gui: None,
             gpu_instance,
             gpu_adapter,
             gpu_device: Arc::new(gpu_device),
             gpu_queue,
         }
     }
+
+    /// Moves the camera eye with currently-held WASD/arrow keys, scaled by `dt` so
+    /// motion speed doesn't depend on the OS's key-repeat rate. Returns whether the
+    /// camera actually moved, so the caller can keep redrawing while a key is held.
+    fn apply_camera_input(&mut self, dt: std::time::Duration) -> bool {
+        use winit::keyboard::KeyCode;
+
+        let Some(camera) = self.camera.as_mut() else {
+            return false;
+        };
+
+        let forward = (camera.target - camera.eye).normalize_or_zero();
+        let right = forward.cross(camera.up).normalize_or_zero();
+
+        let mut offset = glam::Vec3::ZERO;
+        if self.input.is_pressed(KeyCode::KeyW) || self.input.is_pressed(KeyCode::ArrowUp) {
+            offset += forward;
+        }
+        if self.input.is_pressed(KeyCode::KeyS) || self.input.is_pressed(KeyCode::ArrowDown) {
+            offset -= forward;
+        }
+        if self.input.is_pressed(KeyCode::KeyA) || self.input.is_pressed(KeyCode::ArrowLeft) {
+            offset -= right;
+        }
+        if self.input.is_pressed(KeyCode::KeyD) || self.input.is_pressed(KeyCode::ArrowRight) {
+            offset += right;
+        }
+
+        if offset == glam::Vec3::ZERO {
+            return false;
+        }
+
+        camera.eye += offset.normalize_or_zero() * (CAMERA_MOVE_SPEED * dt.as_secs_f32());
+
+        if let Some(gpu_camera) = self.gpu_camera.as_ref() {
+            gpu_camera.update(&self.gpu_queue, camera);
+        }
+
+        true
+    }
+
+    /// Advances animation state by `dt`. Returns whether anything changed, so the
+    /// caller knows whether another redraw is needed.
+    fn update(&mut self, dt: std::time::Duration) -> bool {
+        let moved = self.apply_camera_input(dt);
+
+        if !self.continuous {
+            return moved;
+        }
+
+        let Some(camera) = self.camera.as_mut() else {
+            return moved;
+        };
+
+        let angle = CAMERA_ORBIT_SPEED * dt.as_secs_f32();
+        let rotation = glam::Quat::from_axis_angle(camera.up, angle);
+        camera.eye = camera.target + rotation * (camera.eye - camera.target);
+
+        if let Some(gpu_camera) = self.gpu_camera.as_ref() {
+            gpu_camera.update(&self.gpu_queue, camera);
+        }
+
+        true
+    }
 }
 
 impl ApplicationHandler for Application {
     fn resumed(&mut self, event_loop: &winit::event_loop::ActiveEventLoop) {
-        #[allow(unused_mut)]
-        let mut attributes = Window::default_attributes().with_title(APP_NAME);
-
-        #[cfg(target_arch = "wasm32")]
-        {
-            use winit::platform::web::WindowAttributesExtWebSys;
-            attributes = attributes.with_append(true);
-        }
+        // On Android/mobile the native window is torn down and recreated around
+        // `suspended`/`resumed`; on desktop we only ever go through this once.
+        let window = match self.window.clone() {
+            Some(window) => window,
+            None => {
+                #[allow(unused_mut)]
+                let mut attributes = Window::default_attributes().with_title(APP_NAME);
+
+                #[cfg(target_arch = "wasm32")]
+                {
+                    use winit::platform::web::WindowAttributesExtWebSys;
+                    attributes = attributes.with_append(true);
+                }
 
-        let window = Arc::new(event_loop.create_window(attributes).unwrap());
-        tracing::info!("created window");
+                Arc::new(event_loop.create_window(attributes).unwrap())
+            }
+        };
+        tracing::info!("have window");
 
         let mut size = window.inner_size();
         tracing::info!("size: {size:?}");
@@ -114,11 +244,25 @@ impl ApplicationHandler for Application {
                 ))),
             });
 
+        let camera = self.camera.unwrap_or(camera::Camera {
+            eye: glam::Vec3::new(0.0, 1.0, 2.0),
+            target: glam::Vec3::ZERO,
+            up: glam::Vec3::Y,
+            aspect: size.width as f32 / size.height as f32,
+            fovy: std::f32::consts::FRAC_PI_4,
+            znear: 0.1,
+            zfar: 100.0,
+        });
+        let gpu_camera = self
+            .gpu_camera
+            .take()
+            .unwrap_or_else(|| camera::GpuCamera::new(&self.gpu_device, &camera));
+
         let pipeline_layout =
             self.gpu_device
                 .create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
                     label: None,
-                    bind_group_layouts: &[],
+                    bind_group_layouts: &[&gpu_camera.bind_group_layout],
                     push_constant_ranges: &[],
                 });
 
@@ -133,7 +277,7 @@ impl ApplicationHandler for Application {
                     vertex: wgpu::VertexState {
                         module: &shader,
                         entry_point: "vs_main",
-                        buffers: &[],
+                        buffers: &[mesh::Vertex::layout()],
                         compilation_options: Default::default(),
                     },
                     fragment: Some(wgpu::FragmentState {
@@ -143,15 +287,56 @@ impl ApplicationHandler for Application {
                         targets: &[Some(swapchain_format.into())],
                     }),
                     primitive: wgpu::PrimitiveState::default(),
-                    depth_stencil: None,
+                    depth_stencil: Some(wgpu::DepthStencilState {
+                        format: DEPTH_FORMAT,
+                        depth_write_enabled: true,
+                        depth_compare: wgpu::CompareFunction::Less,
+                        stencil: wgpu::StencilState::default(),
+                        bias: wgpu::DepthBiasState::default(),
+                    }),
                     multisample: wgpu::MultisampleState::default(),
                     multiview: None,
                 });
 
+        let depth_view = create_depth_view(&self.gpu_device, &config);
+
+        let gui = self
+            .gui
+            .take()
+            .unwrap_or_else(|| gui::Gui::new(&self.gpu_device, &window, swapchain_format));
+
+        let mesh = self.mesh.take().unwrap_or_else(|| match std::env::args().nth(1) {
+            Some(path) => mesh::Mesh::load_obj(&self.gpu_device, path),
+            None => mesh::Mesh::from_vertices(&self.gpu_device, &TRIANGLE_VERTICES, &TRIANGLE_INDICES),
+        });
+
         self.window = Some(window);
         self.surface = Some(surface);
         self.surface_config = Some(config);
         self.render_pipeline = Some(render_pipeline);
+        self.depth_view = Some(depth_view);
+        self.mesh = Some(mesh);
+        self.camera = Some(camera);
+        self.gpu_camera = Some(gpu_camera);
+        // Reset rather than preserve: time spent suspended shouldn't show up as a
+        // giant `dt` on the first frame back.
+        self.frame_clock = Some(time::FrameClock::new());
+        self.gui = Some(gui);
+    }
+
+    fn suspended(&mut self, _event_loop: &winit::event_loop::ActiveEventLoop) {
+        tracing::info!("suspended: dropping surface- and window-dependent resources");
+        // On Android the native window backing this `Window` is destroyed when the
+        // activity is paused, and a *different* native window is handed back on the
+        // next `resumed` - so the `Window` itself, not just the surface built on top
+        // of it, must be dropped here and recreated in `resumed`. Device-level
+        // resources (mesh, camera, gui, gpu_device/gpu_queue) don't depend on the
+        // window and are kept so `resumed` doesn't have to redo them.
+        self.render_pipeline = None;
+        self.surface_config = None;
+        self.surface = None;
+        self.depth_view = None;
+        self.window = None;
     }
 
     fn window_event(
@@ -160,6 +345,10 @@ impl ApplicationHandler for Application {
         _window_id: winit::window::WindowId,
         event: winit::event::WindowEvent,
     ) {
+        if let (Some(window), Some(gui)) = (self.window.as_ref(), self.gui.as_mut()) {
+            gui.on_window_event(window, &event);
+        }
+
         match event {
             winit::event::WindowEvent::CloseRequested => {
                 // avoid warning when window is dropped in `windowWillClose`
@@ -169,13 +358,21 @@ impl ApplicationHandler for Application {
             winit::event::WindowEvent::Resized(new_size) => {
                 tracing::info!("resizing too: {new_size:?}");
 
-                let conf = self.surface_config.as_mut().expect("valid config");
+                let (Some(conf), Some(surface)) =
+                    (self.surface_config.as_mut(), self.surface.as_ref())
+                else {
+                    tracing::warn!("resize event with no surface (suspended?)");
+                    return;
+                };
                 conf.width = new_size.width.max(1);
                 conf.height = new_size.height.max(1);
-                self.surface
-                    .as_ref()
-                    .expect("valid config")
-                    .configure(&self.gpu_device, conf);
+                surface.configure(&self.gpu_device, conf);
+                self.depth_view = Some(create_depth_view(&self.gpu_device, conf));
+
+                if let (Some(camera), Some(gpu_camera)) = (self.camera.as_mut(), self.gpu_camera.as_ref()) {
+                    camera.aspect = conf.width as f32 / conf.height as f32;
+                    gpu_camera.update(&self.gpu_queue, camera);
+                }
 
                 //#[cfg(target_arch = "wasm32")]
                 //{
@@ -197,31 +394,113 @@ impl ApplicationHandler for Application {
                     win.request_redraw();
                 }
             }
+            winit::event::WindowEvent::KeyboardInput { event, .. } => {
+                let winit::keyboard::PhysicalKey::Code(key) = event.physical_key else {
+                    return;
+                };
+
+                self.input.set_key(key, event.state.is_pressed());
+
+                if event.state.is_pressed() {
+                    if key == winit::keyboard::KeyCode::KeyC {
+                        self.clear_color_index = (self.clear_color_index + 1) % CLEAR_COLORS.len();
+                    }
+                    if key == winit::keyboard::KeyCode::KeyP {
+                        self.continuous = !self.continuous;
+                        event_loop.set_control_flow(if self.continuous {
+                            ControlFlow::Poll
+                        } else {
+                            ControlFlow::Wait
+                        });
+                    }
+                }
+
+                if let Some(win) = self.window.as_ref() {
+                    win.request_redraw();
+                }
+            }
+            winit::event::WindowEvent::CursorMoved { position, .. } => {
+                self.input.set_cursor_position((position.x, position.y));
+            }
+            winit::event::WindowEvent::CursorLeft { .. } => {
+                self.input.clear_cursor_position();
+            }
+            winit::event::WindowEvent::MouseInput { .. } => {
+                if let Some(win) = self.window.as_ref() {
+                    win.request_redraw();
+                }
+            }
             winit::event::WindowEvent::RedrawRequested => {
                 // prevents a double borrow error?
                 let _ = (&self.gpu_instance, &self.gpu_adapter);
 
+                let dt = self
+                    .frame_clock
+                    .as_mut()
+                    .map(time::FrameClock::tick)
+                    .unwrap_or_default();
+                let moved = self.update(dt);
+
+                // `window`/`surface`/`render_pipeline`/`depth_view` are what `suspended`
+                // clears out (and `mesh`/`gpu_camera` are absent until the first
+                // `resumed`), so a `RedrawRequested` that lands while suspended (a queued
+                // redraw, a compositor race, ...) just skips this frame rather than
+                // tearing down the whole event loop.
                 let Some(window) = self.window.as_ref() else {
                     tracing::warn!("redraw requested on closed window");
-                    event_loop.exit();
                     return;
                 };
 
                 let Some(surface) = self.surface.as_ref() else {
                     tracing::warn!("redraw requested on no surface");
-                    event_loop.exit();
                     return;
                 };
 
                 let Some(render_pipeline) = self.render_pipeline.as_ref() else {
                     tracing::warn!("redraw requested with no pipeline");
-                    event_loop.exit();
                     return;
                 };
 
-                let frame = surface
-                    .get_current_texture()
-                    .expect("Failed to acquire next swap chain texture");
+                let Some(depth_view) = self.depth_view.as_ref() else {
+                    tracing::warn!("redraw requested with no depth buffer");
+                    return;
+                };
+
+                let Some(mesh) = self.mesh.as_ref() else {
+                    tracing::warn!("redraw requested with no mesh loaded");
+                    return;
+                };
+
+                let Some(gpu_camera) = self.gpu_camera.as_ref() else {
+                    tracing::warn!("redraw requested with no camera");
+                    return;
+                };
+
+                let frame = match surface.get_current_texture() {
+                    Ok(frame) => frame,
+                    Err(wgpu::SurfaceError::Lost | wgpu::SurfaceError::Outdated) => {
+                        tracing::warn!("surface lost or outdated, reconfiguring");
+                        let conf = self.surface_config.as_ref().expect("valid config");
+                        surface.configure(&self.gpu_device, conf);
+                        if let Some(win) = self.window.as_ref() {
+                            win.request_redraw();
+                        }
+                        return;
+                    }
+                    Err(wgpu::SurfaceError::OutOfMemory) => {
+                        tracing::error!("surface out of memory, exiting");
+                        event_loop.exit();
+                        return;
+                    }
+                    Err(wgpu::SurfaceError::Timeout) => {
+                        tracing::warn!("surface timeout, skipping frame");
+                        return;
+                    }
+                    Err(wgpu::SurfaceError::Other) => {
+                        tracing::warn!("surface error, skipping frame");
+                        return;
+                    }
+                };
                 let view = frame
                     .texture
                     .create_view(&wgpu::TextureViewDescriptor::default());
@@ -239,22 +518,68 @@ impl ApplicationHandler for Application {
                             view: &view,
                             resolve_target: None,
                             ops: wgpu::Operations {
-                                load: wgpu::LoadOp::Clear(wgpu::Color::GREEN),
+                                load: wgpu::LoadOp::Clear(CLEAR_COLORS[self.clear_color_index]),
                                 store: wgpu::StoreOp::Store,
                             },
                         })],
-                        depth_stencil_attachment: None,
+                        depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment {
+                            view: depth_view,
+                            depth_ops: Some(wgpu::Operations {
+                                load: wgpu::LoadOp::Clear(1.0),
+                                store: wgpu::StoreOp::Store,
+                            }),
+                            stencil_ops: None,
+                        }),
                         timestamp_writes: None,
                         occlusion_query_set: None,
                     });
                     rpass.set_pipeline(render_pipeline);
-                    rpass.draw(0..3, 0..1);
+                    rpass.set_bind_group(0, &gpu_camera.bind_group, &[]);
+                    rpass.set_vertex_buffer(0, mesh.vertex_buffer.slice(..));
+                    rpass.set_index_buffer(mesh.index_buffer.slice(..), wgpu::IndexFormat::Uint32);
+                    rpass.draw_indexed(0..mesh.index_count, 0, 0..1);
+                }
+
+                if let Some(gui) = self.gui.as_mut() {
+                    let conf = self.surface_config.as_ref().expect("valid config");
+                    let screen_descriptor = egui_wgpu::ScreenDescriptor {
+                        size_in_pixels: [conf.width, conf.height],
+                        pixels_per_point: window.scale_factor() as f32,
+                    };
+                    let fps = dt.as_secs_f32().recip();
+                    let clear_color_index = self.clear_color_index;
+                    let adapter_info = self.gpu_adapter.get_info();
+                    let cursor_position = self.input.cursor_position();
+
+                    gui.render(
+                        &self.gpu_device,
+                        &self.gpu_queue,
+                        &mut encoder,
+                        window,
+                        &view,
+                        screen_descriptor,
+                        |ctx| {
+                            egui::Window::new("debug").show(ctx, |ui| {
+                                ui.label(format!("fps: {fps:.0}"));
+                                ui.label(format!("clear color: #{clear_color_index}"));
+                                ui.label(format!("adapter: {}", adapter_info.name));
+                                ui.label(format!("backend: {:?}", adapter_info.backend));
+                                match cursor_position {
+                                    Some((x, y)) => ui.label(format!("cursor: ({x:.0}, {y:.0})")),
+                                    None => ui.label("cursor: outside window"),
+                                };
+                            });
+                        },
+                    );
                 }
 
                 let commands = encoder.finish();
                 self.gpu_queue.submit([commands]);
                 frame.present();
-                //window.request_redraw()
+
+                if self.continuous || moved {
+                    window.request_redraw();
+                }
             }
             _ => (),
         }
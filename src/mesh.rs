@@ -0,0 +1,82 @@
+use wgpu::util::DeviceExt;
+
+/// A single mesh vertex: position and a per-vertex color.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct Vertex {
+    pub position: [f32; 3],
+    pub color: [f32; 3],
+}
+
+impl Vertex {
+    pub const ATTRIBS: [wgpu::VertexAttribute; 2] =
+        wgpu::vertex_attr_array![0 => Float32x3, 1 => Float32x3];
+
+    pub fn layout() -> wgpu::VertexBufferLayout<'static> {
+        wgpu::VertexBufferLayout {
+            array_stride: std::mem::size_of::<Vertex>() as wgpu::BufferAddress,
+            step_mode: wgpu::VertexStepMode::Vertex,
+            attributes: &Self::ATTRIBS,
+        }
+    }
+}
+
+/// GPU-resident vertex/index buffers for a loaded model.
+#[derive(Debug)]
+pub struct Mesh {
+    pub vertex_buffer: wgpu::Buffer,
+    pub index_buffer: wgpu::Buffer,
+    pub index_count: u32,
+}
+
+impl Mesh {
+    pub fn from_vertices(device: &wgpu::Device, vertices: &[Vertex], indices: &[u32]) -> Self {
+        let vertex_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("mesh vertex buffer"),
+            contents: bytemuck::cast_slice(vertices),
+            usage: wgpu::BufferUsages::VERTEX,
+        });
+        let index_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("mesh index buffer"),
+            contents: bytemuck::cast_slice(indices),
+            usage: wgpu::BufferUsages::INDEX,
+        });
+
+        Mesh {
+            vertex_buffer,
+            index_buffer,
+            index_count: indices.len() as u32,
+        }
+    }
+
+    /// Load the first shape of an OBJ file into a [`Mesh`].
+    pub fn load_obj(device: &wgpu::Device, path: impl AsRef<std::path::Path>) -> Self {
+        let (models, _materials) = tobj::load_obj(
+            path.as_ref(),
+            &tobj::LoadOptions {
+                triangulate: true,
+                single_index: true,
+                ..Default::default()
+            },
+        )
+        .expect("failed to load obj file");
+
+        let mesh = &models.first().expect("obj file has no shapes").mesh;
+
+        let vertices: Vec<Vertex> = mesh
+            .positions
+            .chunks_exact(3)
+            .enumerate()
+            .map(|(i, position)| Vertex {
+                position: [position[0], position[1], position[2]],
+                color: mesh
+                    .vertex_color
+                    .get(i * 3..i * 3 + 3)
+                    .map(|c| [c[0], c[1], c[2]])
+                    .unwrap_or([1.0, 1.0, 1.0]),
+            })
+            .collect();
+
+        Self::from_vertices(device, &vertices, &mesh.indices)
+    }
+}
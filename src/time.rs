@@ -0,0 +1,66 @@
+//! Frame timing, abstracted over native (`Instant`) and wasm (`performance.now()`).
+
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Debug)]
+pub struct FrameClock {
+    last: std::time::Instant,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Default for FrameClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl FrameClock {
+    pub fn new() -> Self {
+        FrameClock {
+            last: std::time::Instant::now(),
+        }
+    }
+
+    pub fn tick(&mut self) -> std::time::Duration {
+        let now = std::time::Instant::now();
+        let dt = now.duration_since(self.last);
+        self.last = now;
+        dt
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+#[derive(Debug)]
+pub struct FrameClock {
+    last_ms: f64,
+}
+
+#[cfg(target_arch = "wasm32")]
+impl Default for FrameClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+impl FrameClock {
+    pub fn new() -> Self {
+        FrameClock { last_ms: now_ms() }
+    }
+
+    pub fn tick(&mut self) -> std::time::Duration {
+        let now = now_ms();
+        let dt_ms = (now - self.last_ms).max(0.0);
+        self.last_ms = now;
+        std::time::Duration::from_secs_f64(dt_ms / 1000.0)
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+fn now_ms() -> f64 {
+    web_sys::window()
+        .expect("no global window")
+        .performance()
+        .expect("performance API unavailable")
+        .now()
+}